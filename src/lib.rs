@@ -1,12 +1,66 @@
+#![cfg_attr(feature = "no_std", no_std)]
+
 extern crate byteorder;
-use byteorder::{ByteOrder, LittleEndian};
-use std::{fmt, error};
-use std::io::Write;
 
-#[derive(Clone,Copy,Debug)]
+#[cfg(feature = "no_std")]
+extern crate heapless;
+
+use byteorder::{ByteOrder, BigEndian, LittleEndian};
+
+#[cfg(feature = "no_std")]
+use core::fmt;
+#[cfg(not(feature = "no_std"))]
+use std::fmt;
+#[cfg(not(feature = "no_std"))]
+use std::error;
+
+/// Inline capacity of the `no_std` backing store. Buffers never allocate; writes past this bound
+/// surface as `WriteOverflow`, exactly like the fixed `with_capacity` mode.
+#[cfg(feature = "no_std")]
+pub const CAPACITY: usize = 4096;
+
+/// Backing store for the buffer bytes: a heap `Vec` with `std`, a fixed-capacity inline
+/// `heapless::Vec` under `no_std` so the crate runs without an allocator.
+#[cfg(not(feature = "no_std"))]
+type Storage = Vec<u8>;
+#[cfg(feature = "no_std")]
+type Storage = heapless::Vec<u8, CAPACITY>;
+
+/// Allocate a zero-filled backing store of `cap` bytes.
+#[cfg(not(feature = "no_std"))]
+fn zeroed(cap: usize) -> Storage {
+    vec![0; cap]
+}
+#[cfg(feature = "no_std")]
+fn zeroed(cap: usize) -> Storage {
+    // The inline store cannot exceed CAPACITY; swallowing the resize error here would hand back a
+    // silently zero-length buffer, so fail loudly instead.
+    assert!(
+        cap <= CAPACITY,
+        "requested capacity exceeds the no_std inline CAPACITY"
+    );
+    let mut v = Storage::new();
+    let _ = v.resize(cap, 0);
+    v
+}
+
+/// Copy `bytes` into a fresh backing store.
+#[cfg(not(feature = "no_std"))]
+fn from_slice(bytes: &[u8]) -> Storage {
+    bytes.to_vec()
+}
+#[cfg(feature = "no_std")]
+fn from_slice(bytes: &[u8]) -> Storage {
+    let mut v = Storage::new();
+    let _ = v.extend_from_slice(bytes);
+    v
+}
+
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
 pub enum Error {
     ReadOverflow,
     WriteOverflow,
+    ValueOutOfRange,
 }
 
 impl fmt::Display for Error {
@@ -14,47 +68,217 @@ impl fmt::Display for Error {
         match *self {
             Error::ReadOverflow => write!(fmt, "Error::ReadOverflow"),
             Error::WriteOverflow => write!(fmt, "Error::WriteOverflow"),
+            Error::ValueOutOfRange => write!(fmt, "Error::ValueOutOfRange"),
         }
     }
 }
 
+#[cfg(not(feature = "no_std"))]
 impl error::Error for Error {
     fn description(&self) -> &str {
         match *self {
             Error::ReadOverflow => "buffer overflow for read",
             Error::WriteOverflow => "buffer overflow for write",
+            Error::ValueOutOfRange => "value does not fit the target width",
         }
     }
 
-	fn cause(&self) -> Option<&error::Error> {
-		match *self {
-			_ => None,
-		}
+	fn cause(&self) -> Option<&dyn error::Error> {
+		None
 	}
 }
 
 
+/// Byte order used by the multi-byte numeric accessors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Endian {
+    #[default]
+    Little,
+    Big,
+}
+
+/// Write `$val` into a stack buffer of `$size` bytes using the active byte order, then append it.
+macro_rules! write_number {
+    ($self:ident, $val:expr, $size:expr, $write:ident) => {{
+        let mut buf = [0; $size];
+        match $self.endian {
+            Endian::Little => LittleEndian::$write(&mut buf, $val),
+            Endian::Big => BigEndian::$write(&mut buf, $val),
+        }
+        $self.write_bytes(&buf)
+    }};
+}
+
+/// Read `$size` bytes using the active byte order, advancing the read cursor.
+macro_rules! read_number {
+    ($self:ident, $size:expr, $read:ident) => {{
+        if $self.rpos + $size > $self.data.len() {
+            return Err(Error::ReadOverflow);
+        }
+        let start = $self.rpos;
+        let range = start..start + $size;
+        let val = match $self.endian {
+            Endian::Little => LittleEndian::$read(&$self.data[range.clone()]),
+            Endian::Big => BigEndian::$read(&$self.data[range]),
+        };
+        $self.rpos += $size;
+        $self.update_crc(start, $size);
+        Ok(val)
+    }};
+}
+
+/// Rolling checksum state driven by a precomputed 256-entry table. Installed on demand so that
+/// buffers without CRC tracking pay nothing.
+#[derive(Clone)]
+// Both variants carry an inline 256-entry table so CRC state stays allocator-free under `no_std`;
+// the unavoidable size gap between the u8 and u16 tables is expected.
+#[allow(clippy::large_enum_variant)]
+enum Crc {
+    Crc8 { table: [u8; 256], state: u8, init: u8 },
+    Crc16 { table: [u16; 256], state: u16, init: u16 },
+}
+
+impl Crc {
+    fn new_crc8(poly: u8, init: u8) -> Self {
+        let mut table = [0u8; 256];
+        for (b, slot) in table.iter_mut().enumerate() {
+            let mut crc = b as u8;
+            for _ in 0..8 {
+                if crc & 0x80 != 0 {
+                    crc = (crc << 1) ^ poly;
+                } else {
+                    crc <<= 1;
+                }
+            }
+            *slot = crc;
+        }
+        Crc::Crc8 { table, state: init, init }
+    }
+
+    fn new_crc16(poly: u16, init: u16) -> Self {
+        let mut table = [0u16; 256];
+        for (b, slot) in table.iter_mut().enumerate() {
+            let mut crc = (b as u16) << 8;
+            for _ in 0..8 {
+                if crc & 0x8000 != 0 {
+                    crc = (crc << 1) ^ poly;
+                } else {
+                    crc <<= 1;
+                }
+            }
+            *slot = crc;
+        }
+        Crc::Crc16 { table, state: init, init }
+    }
+
+    fn update(&mut self, byte: u8) {
+        match *self {
+            Crc::Crc8 { ref table, ref mut state, .. } => {
+                *state = table[(*state ^ byte) as usize];
+            }
+            Crc::Crc16 { ref table, ref mut state, .. } => {
+                *state = (*state << 8) ^ table[(((*state >> 8) as u8) ^ byte) as usize];
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        match *self {
+            Crc::Crc8 { ref mut state, init, .. } => *state = init,
+            Crc::Crc16 { ref mut state, init, .. } => *state = init,
+        }
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct ByteBuffer {
-    data: Vec<u8>,
+    data: Storage,
     wpos: usize,
     rpos: usize,
+    endian: Endian,
+    crc: Option<Crc>,
+    auto_grow: bool,
 }
 
 impl ByteBuffer {
+    /// Create an empty, auto-growing buffer. Writes extend the backing store as needed instead of
+    /// failing with `WriteOverflow`; use `with_capacity` for bounded behavior.
+    pub fn new() -> Self {
+        ByteBuffer {
+            data: Storage::new(),
+            wpos: 0,
+            rpos: 0,
+            endian: Endian::Little,
+            crc: None,
+            auto_grow: true,
+        }
+    }
+
+    /// Create a bounded buffer pre-sized to `cap` bytes; writes past `cap` return `WriteOverflow`.
+    ///
+    /// Under the `no_std` feature `cap` must not exceed `CAPACITY`; a larger request panics
+    /// rather than silently yielding a zero-length buffer.
     pub fn with_capacity(cap: usize) -> Self {
         ByteBuffer {
-            data: vec![0; cap],
+            data: zeroed(cap),
             wpos: 0,
             rpos: 0,
+            endian: Endian::Little,
+            crc: None,
+            auto_grow: false,
         }
     }
 
-    /// Return the buffer size
+    /// Create a buffer of the given capacity using `endian` for numeric accessors.
+    ///
+    /// Under the `no_std` feature `cap` must not exceed `CAPACITY`; a larger request panics
+    /// rather than silently yielding a zero-length buffer.
+    pub fn new_with_endian(cap: usize, endian: Endian) -> Self {
+        ByteBuffer {
+            data: zeroed(cap),
+            wpos: 0,
+            rpos: 0,
+            endian,
+            crc: None,
+            auto_grow: false,
+        }
+    }
+
+    /// Toggle auto-growing write mode. When enabled, `write_bytes` extends the buffer rather than
+    /// returning `WriteOverflow`.
+    pub fn set_auto_grow(&mut self, grow: bool) {
+        self.auto_grow = grow;
+    }
+
+    /// Select the byte order honored by all multi-byte numeric methods.
+    pub fn set_endian(&mut self, endian: Endian) {
+        self.endian = endian;
+    }
+
+    /// Return the byte order currently in effect.
+    pub fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    /// Return the buffer size.
+    ///
+    /// In auto-grow mode (`new`) this is the written extent: the backing store grows to the
+    /// furthest position written and is not shrunk when `clear`/`set_wpos` move the write cursor
+    /// back. In bounded mode (`with_capacity`/`new_with_endian`) the store is pre-sized to
+    /// the requested capacity, so `len()` reports that capacity rather than the written extent, and
+    /// reads may consume the still-zeroed tail. Build messages of unknown size with the grow mode
+    /// when you want `len()`/`to_bytes` to cover only what was written.
     pub fn len(&self) -> usize {
         self.data.len()
     }
 
+    /// Return `true` when the buffer holds no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Bytes between the read cursor and `len()`. Subject to the same bounded-mode caveat as
+    /// [`len`](Self::len): in `with_capacity` mode this includes the pre-zeroed tail.
     pub fn read_remain(&self) -> usize {
         self.len() - self.rpos
     }
@@ -62,15 +286,73 @@ impl ByteBuffer {
     pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
         let size = bytes.len() + self.wpos;
         if size > self.data.len() {
-            return Err(Error::WriteOverflow);
+            if self.auto_grow {
+                #[cfg(not(feature = "no_std"))]
+                self.data.resize(size, 0);
+                #[cfg(feature = "no_std")]
+                if self.data.resize(size, 0).is_err() {
+                    return Err(Error::WriteOverflow);
+                }
+            } else {
+                return Err(Error::WriteOverflow);
+            }
         }
         for v in bytes {
             self.data[self.wpos] = *v;
             self.wpos += 1;
+            if let Some(ref mut crc) = self.crc {
+                crc.update(*v);
+            }
         }
         Ok(())
     }
 
+    /// Start tracking a CRC-8 over every byte that subsequently passes through
+    /// `write_bytes`/`read_bytes`, generating the lookup table from `poly` once.
+    pub fn enable_crc8(&mut self, poly: u8, init: u8) {
+        self.crc = Some(Crc::new_crc8(poly, init));
+    }
+
+    /// Start tracking a CRC-16 over every byte that subsequently passes through
+    /// `write_bytes`/`read_bytes`, generating the lookup table from `poly` once.
+    pub fn enable_crc16(&mut self, poly: u16, init: u16) {
+        self.crc = Some(Crc::new_crc16(poly, init));
+    }
+
+    /// Return the running CRC-8 value, or `0` if CRC-8 tracking is not enabled.
+    pub fn current_crc8(&self) -> u8 {
+        match self.crc {
+            Some(Crc::Crc8 { state, .. }) => state,
+            _ => 0,
+        }
+    }
+
+    /// Return the running CRC-16 value, or `0` if CRC-16 tracking is not enabled.
+    pub fn current_crc16(&self) -> u16 {
+        match self.crc {
+            Some(Crc::Crc16 { state, .. }) => state,
+            _ => 0,
+        }
+    }
+
+    /// Reset the running checksum back to its initial value, keeping the table in place.
+    pub fn reset_crc(&mut self) {
+        if let Some(ref mut crc) = self.crc {
+            crc.reset();
+        }
+    }
+
+    /// Feed the `len` bytes starting at `start` through the running checksum, if one is enabled.
+    /// Used by the typed read accessors so header fields read with `read_u16`/`read_tag`/... are
+    /// checksummed exactly like bytes read through `read_bytes`.
+    fn update_crc(&mut self, start: usize, len: usize) {
+        if let Some(ref mut crc) = self.crc {
+            for v in &self.data[start..start + len] {
+                crc.update(*v);
+            }
+        }
+    }
+
     pub fn clear(&mut self) {
         self.wpos = 0;
         self.rpos = 0;
@@ -83,28 +365,123 @@ impl ByteBuffer {
 
     /// Append a word (16 bits value) to the buffer
     pub fn write_u16(&mut self, val: u16) -> Result<(), Error> {
-        let mut buf = [0; 2];
-        LittleEndian::write_u16(&mut buf, val);
-        self.write_bytes(&buf)
+        write_number!(self, val, 2, write_u16)
     }
 
     /// Append a double word (32 bits value) to the buffer
     pub fn write_u32(&mut self, val: u32) -> Result<(), Error> {
-        let mut buf = [0; 4];
-        LittleEndian::write_u32(&mut buf, val);
-        self.write_bytes(&buf)
+        write_number!(self, val, 4, write_u32)
     }
 
+    /// Append a quad word (64 bits value) to the buffer
+    pub fn write_u64(&mut self, val: u64) -> Result<(), Error> {
+        write_number!(self, val, 8, write_u64)
+    }
+
+    /// Append a three-byte value to the buffer in the active byte order.
+    /// Returns `ValueOutOfRange` if `val` does not fit in 24 bits (distinct from the
+    /// `WriteOverflow` raised when the buffer itself has no room left).
+    pub fn write_u24(&mut self, val: u32) -> Result<(), Error> {
+        if val > 0x00FF_FFFF {
+            return Err(Error::ValueOutOfRange);
+        }
+        let b0 = (val & 0xFF) as u8;
+        let b1 = ((val >> 8) & 0xFF) as u8;
+        let b2 = ((val >> 16) & 0xFF) as u8;
+        match self.endian {
+            Endian::Little => self.write_bytes(&[b0, b1, b2]),
+            Endian::Big => self.write_bytes(&[b2, b1, b0]),
+        }
+    }
+
+    /// Append a signed byte (8 bits value) to the buffer
+    pub fn write_i8(&mut self, val: i8) -> Result<(), Error> {
+        self.write_bytes(&[val as u8])
+    }
+
+    /// Append a signed word (16 bits value) to the buffer
+    pub fn write_i16(&mut self, val: i16) -> Result<(), Error> {
+        write_number!(self, val, 2, write_i16)
+    }
+
+    /// Append a signed double word (32 bits value) to the buffer
+    pub fn write_i32(&mut self, val: i32) -> Result<(), Error> {
+        write_number!(self, val, 4, write_i32)
+    }
+
+    /// Append a signed quad word (64 bits value) to the buffer
+    pub fn write_i64(&mut self, val: i64) -> Result<(), Error> {
+        write_number!(self, val, 8, write_i64)
+    }
+
+    /// Append a single-precision float to the buffer
+    pub fn write_f32(&mut self, val: f32) -> Result<(), Error> {
+        write_number!(self, val, 4, write_f32)
+    }
 
-    /// Read a defined amount of raw bytes. The program crash if not enough bytes are available
+    /// Append a double-precision float to the buffer
+    pub fn write_f64(&mut self, val: f64) -> Result<(), Error> {
+        write_number!(self, val, 8, write_f64)
+    }
+
+    /// Append an unsigned LEB128 base-128 varint. Seven value bits are emitted per byte,
+    /// least significant group first, with the high bit (0x80) set on every byte but the last.
+    pub fn write_varint_u64(&mut self, val: u64) -> Result<(), Error> {
+        let mut val = val;
+        loop {
+            let mut byte = (val & 0x7F) as u8;
+            val >>= 7;
+            if val != 0 {
+                byte |= 0x80;
+            }
+            self.write_u8(byte)?;
+            if val == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Append a signed LEB128 varint using zig-zag encoding so small magnitudes stay compact.
+    pub fn write_varint_i64(&mut self, val: i64) -> Result<(), Error> {
+        let zigzag = ((val << 1) ^ (val >> 63)) as u64;
+        self.write_varint_u64(zigzag)
+    }
+
+
+    /// Read a defined amount of raw bytes. Returns `ReadOverflow` if not enough bytes are available.
+    #[cfg(not(feature = "no_std"))]
     pub fn read_bytes(&mut self, size: usize) -> Result<Vec<u8>, Error> {
         if self.rpos + size > self.data.len() {
             return Err(Error::ReadOverflow);
         }
         let range = self.rpos..self.rpos + size;
         let mut res = Vec::<u8>::new();
-        res.write(&self.data[range]).unwrap();
+        res.extend_from_slice(&self.data[range]);
         self.rpos += size;
+        if let Some(ref mut crc) = self.crc {
+            for v in &res {
+                crc.update(*v);
+            }
+        }
+        Ok(res)
+    }
+
+    /// Read a defined amount of raw bytes into an inline `heapless::Vec`. Returns `ReadOverflow`
+    /// if not enough bytes are available.
+    #[cfg(feature = "no_std")]
+    pub fn read_bytes(&mut self, size: usize) -> Result<heapless::Vec<u8, CAPACITY>, Error> {
+        if self.rpos + size > self.data.len() {
+            return Err(Error::ReadOverflow);
+        }
+        let range = self.rpos..self.rpos + size;
+        let mut res = heapless::Vec::<u8, CAPACITY>::new();
+        res.extend_from_slice(&self.data[range]).map_err(|_| Error::ReadOverflow)?;
+        self.rpos += size;
+        if let Some(ref mut crc) = self.crc {
+            for v in &res {
+                crc.update(*v);
+            }
+        }
         Ok(res)
     }
 
@@ -115,27 +492,111 @@ impl ByteBuffer {
         }
         let pos = self.rpos;
         self.rpos += 1;
+        self.update_crc(pos, 1);
         Ok(self.data[pos] as u32)
     }
 
     /// Read a 2-bytes long value. The program crash if not enough bytes are available
     pub fn read_u16_as_u32(&mut self) -> Result<u32, Error> {
-        if self.rpos + 2 >= self.data.len() {
-            return Err(Error::ReadOverflow);
-        }
-        let range = self.rpos..self.rpos + 2;
-        self.rpos += 2;
-        Ok(LittleEndian::read_u16(&self.data[range]) as u32)
+        self.read_u16().map(|v| v as u32)
     }
 
     /// Read a four-bytes long value. The program crash if not enough bytes are available
     pub fn read_u32(&mut self) -> Result<u32, Error> {
-        if self.rpos + 4 >= self.data.len() {
+        read_number!(self, 4, read_u32)
+    }
+
+    /// Read one byte as its natural type
+    pub fn read_u8(&mut self) -> Result<u8, Error> {
+        if self.rpos + 1 > self.data.len() {
             return Err(Error::ReadOverflow);
         }
-        let range = self.rpos..self.rpos + 4;
-        self.rpos += 4;
-        Ok(LittleEndian::read_u32(&self.data[range]))
+        let pos = self.rpos;
+        self.rpos += 1;
+        self.update_crc(pos, 1);
+        Ok(self.data[pos])
+    }
+
+    /// Read a 2-bytes long value as a `u16`
+    pub fn read_u16(&mut self) -> Result<u16, Error> {
+        read_number!(self, 2, read_u16)
+    }
+
+    /// Read an 8-bytes long value as a `u64`
+    pub fn read_u64(&mut self) -> Result<u64, Error> {
+        read_number!(self, 8, read_u64)
+    }
+
+    /// Read a three-byte value in the active byte order, unpacked into a `u32`.
+    pub fn read_u24(&mut self) -> Result<u32, Error> {
+        if self.rpos + 3 > self.data.len() {
+            return Err(Error::ReadOverflow);
+        }
+        let start = self.rpos;
+        let b = &self.data[start..start + 3];
+        let val = match self.endian {
+            Endian::Little => (b[0] as u32) | ((b[1] as u32) << 8) | ((b[2] as u32) << 16),
+            Endian::Big => ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | (b[2] as u32),
+        };
+        self.rpos += 3;
+        self.update_crc(start, 3);
+        Ok(val)
+    }
+
+    /// Read one signed byte
+    pub fn read_i8(&mut self) -> Result<i8, Error> {
+        self.read_u8().map(|v| v as i8)
+    }
+
+    /// Read a 2-bytes long signed value
+    pub fn read_i16(&mut self) -> Result<i16, Error> {
+        read_number!(self, 2, read_i16)
+    }
+
+    /// Read a four-bytes long signed value
+    pub fn read_i32(&mut self) -> Result<i32, Error> {
+        read_number!(self, 4, read_i32)
+    }
+
+    /// Read an 8-bytes long signed value
+    pub fn read_i64(&mut self) -> Result<i64, Error> {
+        read_number!(self, 8, read_i64)
+    }
+
+    /// Read a single-precision float
+    pub fn read_f32(&mut self) -> Result<f32, Error> {
+        read_number!(self, 4, read_f32)
+    }
+
+    /// Read a double-precision float
+    pub fn read_f64(&mut self) -> Result<f64, Error> {
+        read_number!(self, 8, read_f64)
+    }
+
+    /// Read an unsigned LEB128 varint. Returns `ReadOverflow` if the buffer ends mid-varint, or
+    /// if the value is not terminated within the 10 bytes a 64-bit integer can occupy.
+    pub fn read_varint_u64(&mut self) -> Result<u64, Error> {
+        let mut result: u64 = 0;
+        let mut shift = 0;
+        for i in 0..10 {
+            let byte = self.read_u8()?;
+            // The tenth byte may only contribute the final bit of a 64-bit value.
+            if i == 9 && byte > 0x01 {
+                return Err(Error::ReadOverflow);
+            }
+            result |= ((byte & 0x7F) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+            shift += 7;
+        }
+        Err(Error::ReadOverflow)
+    }
+
+    /// Read a zig-zag encoded signed LEB128 varint.
+    pub fn read_varint_i64(&mut self) -> Result<i64, Error> {
+        let u = self.read_varint_u64()?;
+        Ok(((u >> 1) as i64) ^ -((u & 1) as i64))
     }
 
     /// Return the position of the reading cursor
@@ -143,13 +604,358 @@ impl ByteBuffer {
         self.rpos
     }
 
+    /// Move the reading cursor, clamping it within `len()`.
+    pub fn set_rpos(&mut self, pos: usize) {
+        self.rpos = if pos > self.len() { self.len() } else { pos };
+    }
+
     /// Return the writing cursor position
     pub fn get_wpos(&self) -> usize {
         self.wpos
     }
 
-    /// Return the raw byte buffer.
+    /// Move the writing cursor, clamping it within `len()`.
+    pub fn set_wpos(&mut self, pos: usize) {
+        self.wpos = if pos > self.len() { self.len() } else { pos };
+    }
+
+    /// Read the next byte without advancing the read cursor.
+    pub fn peek_u8(&self) -> Result<u8, Error> {
+        if self.rpos + 1 > self.data.len() {
+            return Err(Error::ReadOverflow);
+        }
+        Ok(self.data[self.rpos])
+    }
+
+    /// Borrow the next `n` bytes without advancing the read cursor.
+    pub fn peek_bytes(&self, n: usize) -> Result<&[u8], Error> {
+        if self.rpos + n > self.data.len() {
+            return Err(Error::ReadOverflow);
+        }
+        Ok(&self.data[self.rpos..self.rpos + n])
+    }
+
+    /// Read a four-byte tag (as used for FourCC / container tags), advancing the read cursor.
+    pub fn read_tag(&mut self) -> Result<[u8; 4], Error> {
+        if self.rpos + 4 > self.data.len() {
+            return Err(Error::ReadOverflow);
+        }
+        let start = self.rpos;
+        let mut tag = [0u8; 4];
+        tag.copy_from_slice(&self.data[start..start + 4]);
+        self.rpos += 4;
+        self.update_crc(start, 4);
+        Ok(tag)
+    }
+
+    /// Return the raw byte buffer as an owned heap `Vec`.
+    #[cfg(not(feature = "no_std"))]
     pub fn to_bytes(&self) -> Vec<u8> {
         self.data.to_vec()
     }
+
+    /// Borrow the backing store as a slice (no allocation). Covers `len()` bytes, so in bounded
+    /// mode this includes the still-zeroed tail.
+    #[cfg(feature = "no_std")]
+    pub fn to_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Create an independent view over bytes `[start, end)`, cloning the windowed region into a
+    /// fresh buffer with its own read cursor. Reads past `end` return `ReadOverflow` even when the
+    /// parent holds more data, and `get_rpos`/`read_remain` are reported relative to the window.
+    /// Useful for handing a length-prefixed sub-record to a sub-parser without position math.
+    pub fn view(&self, start: usize, end: usize) -> ByteBuffer {
+        let end = if end > self.data.len() { self.data.len() } else { end };
+        let start = if start > end { end } else { start };
+        let data = from_slice(&self.data[start..end]);
+        let len = data.len();
+        ByteBuffer {
+            data,
+            wpos: len,
+            rpos: 0,
+            endian: self.endian,
+            crc: None,
+            auto_grow: false,
+        }
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl std::io::Read for ByteBuffer {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let remain = self.data.len() - self.rpos;
+        let size = if buf.len() < remain { buf.len() } else { remain };
+        buf[..size].copy_from_slice(&self.data[self.rpos..self.rpos + size]);
+        self.rpos += size;
+        if let Some(ref mut crc) = self.crc {
+            for v in &buf[..size] {
+                crc.update(*v);
+            }
+        }
+        Ok(size)
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl std::io::Write for ByteBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.auto_grow && self.wpos + buf.len() > self.data.len() {
+            self.data.resize(self.wpos + buf.len(), 0);
+        }
+        let remain = self.data.len() - self.wpos;
+        let size = if buf.len() < remain { buf.len() } else { remain };
+        for v in &buf[..size] {
+            self.data[self.wpos] = *v;
+            self.wpos += 1;
+            if let Some(ref mut crc) = self.crc {
+                crc.update(*v);
+            }
+        }
+        Ok(size)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl std::io::Seek for ByteBuffer {
+    /// Move the read cursor, clamping the result within `len()`.
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        use std::io::SeekFrom;
+        let len = self.len() as i64;
+        let target = match pos {
+            SeekFrom::Start(n) => n as i64,
+            SeekFrom::End(n) => len + n,
+            SeekFrom::Current(n) => self.rpos as i64 + n,
+        };
+        let clamped = if target < 0 {
+            0
+        } else if target > len {
+            len
+        } else {
+            target
+        };
+        self.rpos = clamped as usize;
+        Ok(self.rpos as u64)
+    }
+}
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_width_round_trips_both_orders() {
+        for endian in [Endian::Little, Endian::Big] {
+            let mut buf = ByteBuffer::new();
+            buf.set_endian(endian);
+            buf.write_u16(0x0102).unwrap();
+            buf.write_u32(0x0102_0304).unwrap();
+            buf.write_u64(0x0102_0304_0506_0708).unwrap();
+            buf.write_i16(-2).unwrap();
+            buf.write_i32(-3).unwrap();
+            buf.write_i64(-4).unwrap();
+            buf.write_f32(1.5).unwrap();
+            buf.write_f64(-2.5).unwrap();
+
+            assert_eq!(buf.read_u16().unwrap(), 0x0102);
+            assert_eq!(buf.read_u32().unwrap(), 0x0102_0304);
+            assert_eq!(buf.read_u64().unwrap(), 0x0102_0304_0506_0708);
+            assert_eq!(buf.read_i16().unwrap(), -2);
+            assert_eq!(buf.read_i32().unwrap(), -3);
+            assert_eq!(buf.read_i64().unwrap(), -4);
+            assert_eq!(buf.read_f32().unwrap(), 1.5);
+            assert_eq!(buf.read_f64().unwrap(), -2.5);
+        }
+    }
+
+    #[test]
+    fn big_endian_byte_layout_differs_from_little() {
+        let mut be = ByteBuffer::new();
+        be.set_endian(Endian::Big);
+        be.write_u32(0x0102_0304).unwrap();
+        assert_eq!(be.to_bytes(), vec![0x01, 0x02, 0x03, 0x04]);
+
+        let mut le = ByteBuffer::new();
+        le.write_u32(0x0102_0304).unwrap();
+        assert_eq!(le.to_bytes(), vec![0x04, 0x03, 0x02, 0x01]);
+    }
+
+    #[test]
+    fn u24_round_trips_in_active_order() {
+        for endian in [Endian::Little, Endian::Big] {
+            let mut buf = ByteBuffer::new();
+            buf.set_endian(endian);
+            buf.write_u24(0x00AB_CDEF).unwrap();
+            assert_eq!(buf.len(), 3);
+            assert_eq!(buf.read_u24().unwrap(), 0x00AB_CDEF);
+        }
+    }
+
+    #[test]
+    fn u24_rejects_out_of_range_value() {
+        let mut buf = ByteBuffer::new();
+        assert_eq!(buf.write_u24(0x0100_0000), Err(Error::ValueOutOfRange));
+        // The buffer had room, so this is not a WriteOverflow and nothing was written.
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn varint_u64_round_trips() {
+        for val in [0u64, 1, 127, 128, 300, 16_384, u32::MAX as u64, u64::MAX] {
+            let mut buf = ByteBuffer::new();
+            buf.write_varint_u64(val).unwrap();
+            assert_eq!(buf.read_varint_u64().unwrap(), val);
+        }
+    }
+
+    #[test]
+    fn varint_i64_round_trips_including_extremes() {
+        for val in [0i64, -1, 1, -300, 300, i64::MIN, i64::MAX] {
+            let mut buf = ByteBuffer::new();
+            buf.write_varint_i64(val).unwrap();
+            assert_eq!(buf.read_varint_i64().unwrap(), val);
+        }
+    }
+
+    #[test]
+    fn varint_small_values_stay_compact() {
+        let mut buf = ByteBuffer::new();
+        buf.write_varint_i64(-1).unwrap();
+        // zig-zag maps -1 to 1, a single byte.
+        assert_eq!(buf.len(), 1);
+    }
+
+    #[test]
+    fn varint_truncated_stream_errors() {
+        // A lone continuation byte promises more that never arrives.
+        let mut buf = ByteBuffer::new();
+        buf.write_u8(0x80).unwrap();
+        assert_eq!(buf.read_varint_u64(), Err(Error::ReadOverflow));
+    }
+
+    #[test]
+    fn varint_overlong_input_errors() {
+        // Ten continuation bytes plus a terminator make an 11-byte varint, past the 10-byte cap
+        // for a 64-bit value.
+        let mut buf = ByteBuffer::new();
+        for _ in 0..10 {
+            buf.write_u8(0x80).unwrap();
+        }
+        buf.write_u8(0x00).unwrap();
+        assert_eq!(buf.read_varint_u64(), Err(Error::ReadOverflow));
+    }
+
+    // CRC-16/CCITT-FALSE: poly 0x1021, init 0xFFFF. The check value over "123456789" is 0x29B1.
+    #[test]
+    fn crc16_ccitt_check_vector_on_write() {
+        let mut buf = ByteBuffer::new();
+        buf.enable_crc16(0x1021, 0xFFFF);
+        buf.write_bytes(b"123456789").unwrap();
+        assert_eq!(buf.current_crc16(), 0x29B1);
+    }
+
+    #[test]
+    fn typed_reads_update_crc() {
+        // Same check vector, but consumed through typed accessors instead of read_bytes.
+        let mut buf = ByteBuffer::new();
+        buf.write_bytes(b"123456789").unwrap();
+        buf.enable_crc16(0x1021, 0xFFFF);
+        buf.read_u32().unwrap();
+        buf.read_u32().unwrap();
+        buf.read_u8().unwrap();
+        assert_eq!(buf.current_crc16(), 0x29B1);
+    }
+
+    #[test]
+    fn reset_crc_restores_initial_state() {
+        let mut buf = ByteBuffer::new();
+        buf.enable_crc16(0x1021, 0xFFFF);
+        buf.write_bytes(b"123456789").unwrap();
+        buf.reset_crc();
+        assert_eq!(buf.current_crc16(), 0xFFFF);
+    }
+
+    #[test]
+    fn auto_grow_extends_and_tracks_written_extent() {
+        let mut buf = ByteBuffer::new();
+        buf.write_bytes(&[1, 2, 3]).unwrap();
+        buf.write_bytes(&[4, 5]).unwrap();
+        // Grow mode tracks exactly what was written.
+        assert_eq!(buf.len(), 5);
+        assert_eq!(buf.to_bytes(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn bounded_mode_overflows_instead_of_growing() {
+        let mut buf = ByteBuffer::with_capacity(2);
+        assert_eq!(buf.write_bytes(&[1, 2, 3]), Err(Error::WriteOverflow));
+        // Opting into growth makes the same write succeed.
+        buf.set_auto_grow(true);
+        assert!(buf.write_bytes(&[1, 2, 3]).is_ok());
+    }
+
+    #[test]
+    fn io_traits_round_trip() {
+        use std::io::{Read, Seek, SeekFrom, Write};
+
+        let mut buf = ByteBuffer::new();
+        buf.write_all(&[10, 20, 30, 40]).unwrap();
+
+        let mut out = [0u8; 2];
+        assert_eq!(buf.read(&mut out).unwrap(), 2);
+        assert_eq!(out, [10, 20]);
+
+        buf.seek(SeekFrom::Start(0)).unwrap();
+        let mut all = [0u8; 4];
+        buf.read_exact(&mut all).unwrap();
+        assert_eq!(all, [10, 20, 30, 40]);
+    }
+
+    #[test]
+    fn peeks_do_not_advance_the_cursor() {
+        let mut buf = ByteBuffer::new();
+        buf.write_bytes(b"RIFF\x01").unwrap();
+
+        assert_eq!(buf.peek_u8().unwrap(), b'R');
+        assert_eq!(buf.peek_bytes(4).unwrap(), b"RIFF");
+        assert_eq!(buf.get_rpos(), 0);
+
+        assert_eq!(&buf.read_tag().unwrap(), b"RIFF");
+        assert_eq!(buf.get_rpos(), 4);
+    }
+
+    #[test]
+    fn view_exposes_only_its_window() {
+        let mut buf = ByteBuffer::new();
+        buf.write_bytes(&[0, 1, 2, 3, 4, 5]).unwrap();
+
+        let mut view = buf.view(2, 5);
+        assert_eq!(view.read_remain(), 3);
+        assert_eq!(view.read_bytes(3).unwrap(), vec![2, 3, 4]);
+        // The parent held byte 5, but the view is bounded at its end.
+        assert_eq!(view.get_rpos(), 3);
+    }
+
+    #[test]
+    fn view_reads_past_end_overflow() {
+        let mut buf = ByteBuffer::new();
+        buf.write_bytes(&[0, 1, 2, 3, 4, 5]).unwrap();
+
+        let mut view = buf.view(2, 5);
+        assert_eq!(view.read_bytes(4), Err(Error::ReadOverflow));
+    }
+
+    #[test]
+    fn view_inherits_parent_endian() {
+        let mut buf = ByteBuffer::new();
+        buf.set_endian(Endian::Big);
+        buf.write_bytes(&[0x01, 0x02]).unwrap();
+
+        let mut view = buf.view(0, 2);
+        assert_eq!(view.read_u16().unwrap(), 0x0102);
+    }
 }